@@ -5,10 +5,20 @@
 //! Operations that benefit from Rust:
 //! - Accession number parsing (no GIL, SIMD-friendly)
 //! - Document boundary detection (parallel search)
+//! - Full submission tree parsing (single-pass, no re-scans)
 //! - Content hashing (parallel FNV-1a)
 //! - Large file splitting (memory-mapped, parallel)
+//! - Overlap-safe document boundary merging
+//! - Content-defined chunking for cross-filing deduplication
 
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use memmap2::Mmap;
+use pyo3::exceptions::PyIOError;
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
 use rayon::prelude::*;
 
 /// Parse SEC accession number: 0000320193-24-000081
@@ -59,44 +69,217 @@ fn parse_accession_batch(accessions: Vec<String>) -> Vec<Option<(String, u8, u32
         .collect()
 }
 
-/// Find document boundaries in SEC complete submission
-/// Returns Vec<(start, end)> byte positions
+/// Find document boundaries in SEC complete submission.
+/// Returns Vec<(start, end)> byte positions, assuming sequential,
+/// non-overlapping `<DOCUMENT>...</DOCUMENT>` pairs (use
+/// `merged_document_boundaries` for nested/malformed input).
+///
+/// Locates every `<DOCUMENT>`/`</DOCUMENT>` occurrence with a single
+/// Aho-Corasick pass over `data` via `find_all`, then walks that (much
+/// smaller) match list once to pair each start with the next end at or after
+/// it — one O(n) walk over the buffer instead of a `find_subsequence` call
+/// per tag per document.
 #[pyfunction]
 fn find_document_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
     const START_TAG: &[u8] = b"<DOCUMENT>";
     const END_TAG: &[u8] = b"</DOCUMENT>";
-    
+    const START_ID: usize = 0;
+    const END_ID: usize = 1;
+
+    let automaton = AhoCorasick::new(&[START_TAG, END_TAG]);
+    let mut matches = automaton.find_all(data);
+    matches.sort_by_key(|&(_, start)| start);
+
     let mut boundaries = Vec::new();
     let mut pos = 0;
-    
-    while pos < data.len() {
-        // Find start tag
-        if let Some(start_offset) = find_subsequence(&data[pos..], START_TAG) {
-            let doc_start = pos + start_offset;
-            
-            // Find end tag after start
-            let search_start = doc_start + START_TAG.len();
-            if let Some(end_offset) = find_subsequence(&data[search_start..], END_TAG) {
-                let doc_end = search_start + end_offset + END_TAG.len();
-                boundaries.push((doc_start, doc_end));
-                pos = doc_end;
-            } else {
-                break;
-            }
-        } else {
+    let mut i = 0;
+
+    while i < matches.len() {
+        while i < matches.len() && !(matches[i].0 == START_ID && matches[i].1 >= pos) {
+            i += 1;
+        }
+        if i >= matches.len() {
+            break;
+        }
+        let doc_start = matches[i].1;
+        let search_from = doc_start + START_TAG.len();
+        i += 1;
+
+        while i < matches.len() && !(matches[i].0 == END_ID && matches[i].1 >= search_from) {
+            i += 1;
+        }
+        if i >= matches.len() {
             break;
         }
+        let doc_end = matches[i].1 + END_TAG.len();
+        boundaries.push((doc_start, doc_end));
+        pos = doc_end;
+        i += 1;
     }
-    
+
     boundaries
 }
 
-/// Find subsequence in byte slice (KMP would be faster for repeated patterns)
+/// Find subsequence in byte slice using Boyer-Moore-Horspool.
+///
+/// Precomputes a 256-entry bad-character skip table so mismatches advance the
+/// needle by more than one byte, then scans right-to-left at each alignment.
+/// This is O(n) in practice versus the O(n*m) `windows().position()` it
+/// replaces, which matters once this is called per-tag over multi-hundred-MB
+/// submissions.
 #[inline]
 fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
-    haystack
-        .windows(needle.len())
-        .position(|window| window == needle)
+    let m = needle.len();
+    if m == 0 {
+        return Some(0);
+    }
+    if haystack.len() < m {
+        return None;
+    }
+
+    let mut skip = [m; 256];
+    for (i, &b) in needle.iter().enumerate().take(m - 1) {
+        skip[b as usize] = m - 1 - i;
+    }
+
+    let last = needle[m - 1];
+    let mut pos = 0;
+    while pos + m <= haystack.len() {
+        // Cheap check against the needle's last byte before paying for a
+        // full right-to-left compare.
+        let window = &haystack[pos..pos + m];
+        if window[m - 1] == last && window[..m - 1].iter().rev().eq(needle[..m - 1].iter().rev()) {
+            return Some(pos);
+        }
+        pos += skip[haystack[pos + m - 1] as usize];
+    }
+    None
+}
+
+/// One match emitted by [`AhoCorasick`]: which pattern matched and where it starts.
+type TagMatch = (usize, usize);
+
+/// Multi-pattern matcher that locates every occurrence of a set of byte
+/// patterns in a single forward pass over the haystack.
+///
+/// Built as a trie over the pattern bytes with Aho-Corasick failure links, so
+/// `find_all` is O(n + total matches) regardless of how many tags are
+/// searched for, instead of O(n * patterns) from running `find_subsequence`
+/// once per tag.
+struct AhoCorasick {
+    /// `goto[node][byte]` child node, or `usize::MAX` if absent.
+    goto_table: Vec<[usize; 256]>,
+    /// Failure link for each node: longest proper suffix that is also a prefix.
+    fail: Vec<usize>,
+    /// Pattern ids that end at this node, directly or via a fail/suffix link.
+    outputs: Vec<Vec<usize>>,
+    /// Length of each registered pattern, indexed by pattern id.
+    pattern_lens: Vec<usize>,
+}
+
+const AC_NONE: usize = usize::MAX;
+
+impl AhoCorasick {
+    fn new(patterns: &[&[u8]]) -> Self {
+        let mut goto_table = vec![[AC_NONE; 256]];
+        let mut outputs = vec![Vec::new()];
+        let mut pattern_lens = Vec::with_capacity(patterns.len());
+
+        // Build the trie.
+        for (pattern_id, pattern) in patterns.iter().enumerate() {
+            pattern_lens.push(pattern.len());
+            let mut node = 0;
+            for &b in pattern.iter() {
+                let next = goto_table[node][b as usize];
+                if next == AC_NONE {
+                    goto_table.push([AC_NONE; 256]);
+                    outputs.push(Vec::new());
+                    let new_node = goto_table.len() - 1;
+                    goto_table[node][b as usize] = new_node;
+                    node = new_node;
+                } else {
+                    node = next;
+                }
+            }
+            outputs[node].push(pattern_id);
+        }
+
+        // Compute failure links with a BFS over the trie.
+        let mut fail = vec![0usize; goto_table.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for b in 0..256 {
+            let child = goto_table[0][b];
+            if child != AC_NONE {
+                fail[child] = 0;
+                queue.push_back(child);
+            }
+        }
+        while let Some(node) = queue.pop_front() {
+            for b in 0..256 {
+                let child = goto_table[node][b];
+                if child == AC_NONE {
+                    continue;
+                }
+                let mut down = fail[node];
+                while goto_table[down][b] == AC_NONE && down != 0 {
+                    down = fail[down];
+                }
+                let suffix_link = goto_table[down][b];
+                fail[child] = if suffix_link != AC_NONE && suffix_link != child {
+                    suffix_link
+                } else {
+                    0
+                };
+                let inherited = outputs[fail[child]].clone();
+                outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+
+        AhoCorasick {
+            goto_table,
+            fail,
+            outputs,
+            pattern_lens,
+        }
+    }
+
+    /// Scan `haystack` once, emitting `(pattern_id, start_pos)` for every match
+    /// of every registered pattern, in the order their matches end.
+    fn find_all(&self, haystack: &[u8]) -> Vec<TagMatch> {
+        let mut matches = Vec::new();
+        let mut node = 0;
+        for (i, &b) in haystack.iter().enumerate() {
+            while self.goto_table[node][b as usize] == AC_NONE && node != 0 {
+                node = self.fail[node];
+            }
+            let next = self.goto_table[node][b as usize];
+            node = if next != AC_NONE { next } else { 0 };
+
+            for &pattern_id in &self.outputs[node] {
+                let end_pos = i + 1;
+                let start_pos = end_pos - self.pattern_lens[pattern_id];
+                matches.push((pattern_id, start_pos));
+            }
+        }
+        matches
+    }
+}
+
+/// Locate every occurrence of every needle in `data` in a single pass.
+///
+/// Returns `(pattern_id, start_pos)` pairs, where `pattern_id` is the index
+/// of the matching needle in `needles`, in the order their matches end (not
+/// sorted by start position — with mixed-length needles a later-starting,
+/// shorter match can end before an earlier-starting, longer one).
+/// Use this instead of calling `find_subsequence` once per tag when you need
+/// several tags (`<DOCUMENT>`, `</DOCUMENT>`, `<TYPE>`, ...) located over the
+/// same buffer, since it pays for one O(n) walk regardless of pattern count.
+#[pyfunction]
+fn find_all(data: &[u8], needles: Vec<Vec<u8>>) -> Vec<(usize, usize)> {
+    let refs: Vec<&[u8]> = needles.iter().map(|n| n.as_slice()).collect();
+    let automaton = AhoCorasick::new(&refs);
+    automaton.find_all(data)
 }
 
 /// Split complete submission into documents (parallel search for boundaries)
@@ -138,25 +321,524 @@ fn count_lines(data: &[u8]) -> usize {
     data.par_iter().filter(|&&b| b == b'\n').count() + 1
 }
 
-/// Extract content between <TAG> and </TAG>
+/// Extract content between <TAG> and </TAG>.
+///
+/// Locates both the open and close tag with a single Aho-Corasick pass over
+/// `data` via `find_all`, rather than two independent scans.
 #[pyfunction]
 fn extract_tag_content<'py>(
     py: Python<'py>,
     data: &[u8],
     tag_name: &str,
 ) -> Option<&'py pyo3::types::PyBytes> {
+    const START_ID: usize = 0;
+    const END_ID: usize = 1;
+
     let start_tag = format!("<{}>", tag_name);
     let end_tag = format!("</{}>", tag_name);
-    
-    let start_pos = find_subsequence(data, start_tag.as_bytes())?;
+
+    let automaton = AhoCorasick::new(&[start_tag.as_bytes(), end_tag.as_bytes()]);
+    let mut matches = automaton.find_all(data);
+    matches.sort_by_key(|&(_, start)| start);
+
+    let &(_, start_pos) = matches.iter().find(|&&(pattern_id, _)| pattern_id == START_ID)?;
     let content_start = start_pos + start_tag.len();
-    
-    let end_pos = find_subsequence(&data[content_start..], end_tag.as_bytes())?;
-    let content = &data[content_start..content_start + end_pos];
-    
+
+    let &(_, end_pos) = matches
+        .iter()
+        .find(|&&(pattern_id, start)| pattern_id == END_ID && start >= content_start)?;
+    let content = &data[content_start..end_pos];
+
     Some(pyo3::types::PyBytes::new(py, content))
 }
 
+/// A node in the [`SubmissionTree`] arena: a container scope (`SEC-HEADER`,
+/// `DOCUMENT`, `TEXT`) or a single-line metadata field (`TYPE`, `SEQUENCE`,
+/// `FILENAME`, `DESCRIPTION`).
+struct SubmissionNode {
+    tag: &'static str,
+    content_start: usize,
+    content_end: usize,
+    parent: Option<u32>,
+    children: Vec<u32>,
+}
+
+/// The full SGML block structure of a complete submission, resolved in a
+/// single forward pass instead of one re-scan per tag per document.
+struct SubmissionTree {
+    nodes: Vec<SubmissionNode>,
+    root: u32,
+}
+
+/// Tags that open/close a container scope, in the order their patterns are
+/// registered with the [`AhoCorasick`] automaton below.
+const CONTAINER_OPEN_TAGS: [(&[u8], &str); 3] = [
+    (b"<SEC-HEADER>", "SEC-HEADER"),
+    (b"<DOCUMENT>", "DOCUMENT"),
+    (b"<TEXT>", "TEXT"),
+];
+const CONTAINER_CLOSE_TAGS: [(&[u8], &str); 3] = [
+    (b"</SEC-HEADER>", "SEC-HEADER"),
+    (b"</DOCUMENT>", "DOCUMENT"),
+    (b"</TEXT>", "TEXT"),
+];
+const FIELD_TAGS: [(&[u8], &str); 4] = [
+    (b"<TYPE>", "TYPE"),
+    (b"<SEQUENCE>", "SEQUENCE"),
+    (b"<FILENAME>", "FILENAME"),
+    (b"<DESCRIPTION>", "DESCRIPTION"),
+];
+
+/// Resolve the entire SGML block structure of `data` in one forward pass.
+///
+/// Locates every relevant tag with a single Aho-Corasick scan, then walks the
+/// matches left to right maintaining a spine stack of currently-open
+/// container tags: an open tag pushes a node onto a growable arena and onto
+/// the spine, a close tag pops the spine and records the node's content end,
+/// and single-line fields (`<TYPE>`, `<SEQUENCE>`, ...) are attached to
+/// whatever container is currently open, with their value read up to the
+/// next newline since they have no closing tag in EDGAR SGML. A close tag
+/// only pops when it matches the tag currently on top of the spine, and the
+/// root is never popped — this keeps a stray or mismatched close tag (e.g.
+/// tag-like bytes inside a `<TEXT>` payload that the scan doesn't skip) from
+/// panicking or corrupting the tree; it's simply ignored.
+fn parse_submission_tree(data: &[u8]) -> SubmissionTree {
+    let mut patterns: Vec<&[u8]> = Vec::with_capacity(10);
+    patterns.extend(CONTAINER_OPEN_TAGS.iter().map(|&(pat, _)| pat));
+    patterns.extend(CONTAINER_CLOSE_TAGS.iter().map(|&(pat, _)| pat));
+    patterns.extend(FIELD_TAGS.iter().map(|&(pat, _)| pat));
+
+    let open_count = CONTAINER_OPEN_TAGS.len();
+    let close_count = CONTAINER_CLOSE_TAGS.len();
+
+    let automaton = AhoCorasick::new(&patterns);
+    let mut matches = automaton.find_all(data);
+    matches.sort_by_key(|&(_, start)| start);
+
+    let mut nodes = Vec::with_capacity(data.len() / 64 + 16);
+    nodes.push(SubmissionNode {
+        tag: "ROOT",
+        content_start: 0,
+        content_end: data.len(),
+        parent: None,
+        children: Vec::new(),
+    });
+    let root = 0u32;
+    let mut spine: Vec<u32> = vec![root];
+
+    for (pattern_id, start) in matches {
+        if pattern_id < open_count {
+            let (pattern, tag) = CONTAINER_OPEN_TAGS[pattern_id];
+            let content_start = start + pattern.len();
+            let parent = *spine.last().unwrap();
+            let idx = nodes.len() as u32;
+            nodes.push(SubmissionNode {
+                tag,
+                content_start,
+                content_end: content_start,
+                parent: Some(parent),
+                children: Vec::new(),
+            });
+            nodes[parent as usize].children.push(idx);
+            spine.push(idx);
+        } else if pattern_id < open_count + close_count {
+            let (_, expected_tag) = CONTAINER_CLOSE_TAGS[pattern_id - open_count];
+            if spine.len() > 1 {
+                let top = *spine.last().unwrap();
+                if nodes[top as usize].tag == expected_tag {
+                    spine.pop();
+                    nodes[top as usize].content_end = start;
+                }
+            }
+        } else {
+            let (pattern, tag) = FIELD_TAGS[pattern_id - open_count - close_count];
+            let content_start = start + pattern.len();
+            let content_end = data[content_start..]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(data.len(), |o| content_start + o);
+            let parent = *spine.last().unwrap();
+            let idx = nodes.len() as u32;
+            nodes.push(SubmissionNode {
+                tag,
+                content_start,
+                content_end,
+                parent: Some(parent),
+                children: Vec::new(),
+            });
+            nodes[parent as usize].children.push(idx);
+        }
+    }
+
+    SubmissionTree { nodes, root }
+}
+
+/// Find the first child of `node` with the given tag name.
+fn find_child<'a>(tree: &'a SubmissionTree, node: u32, tag: &str) -> Option<&'a SubmissionNode> {
+    tree.nodes[node as usize]
+        .children
+        .iter()
+        .map(|&idx| &tree.nodes[idx as usize])
+        .find(|child| child.tag == tag)
+}
+
+fn field_value(data: &[u8], node: &SubmissionNode) -> String {
+    String::from_utf8_lossy(&data[node.content_start..node.content_end])
+        .trim()
+        .to_string()
+}
+
+/// Parse the `KEY:\tVALUE` lines inside a `<SEC-HEADER>` block (e.g.
+/// `ACCESSION NUMBER:`, `CONFORMED SUBMISSION TYPE:`, `FILED AS OF DATE:`)
+/// into a key/value map, so callers don't have to re-scan the header region
+/// themselves.
+fn parse_header_fields(data: &[u8], header: &SubmissionNode) -> HashMap<String, String> {
+    let text = String::from_utf8_lossy(&data[header.content_start..header.content_end]);
+    let mut fields = HashMap::new();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('<') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            let key = key.trim();
+            let value = value.trim();
+            if !key.is_empty() && !value.is_empty() {
+                fields.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+    fields
+}
+
+/// Parse a complete SEC submission into its full document tree in one pass.
+///
+/// Returns a dict with the `<SEC-HEADER>` byte range and its parsed
+/// `KEY: VALUE` fields under `"header"`, and an ordered `"documents"` list,
+/// one entry per `<DOCUMENT>`, each exposing `type`, `sequence`, `filename`,
+/// `description`, and the `(start, end)` byte range of its `<TEXT>` payload.
+/// Callers get all per-document and per-header metadata from this single
+/// structure instead of re-scanning each region.
+#[pyfunction]
+fn parse_submission<'py>(py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyDict>> {
+    let tree = parse_submission_tree(data);
+
+    let result = PyDict::new(py);
+
+    let header = find_child(&tree, tree.root, "SEC-HEADER");
+    match header {
+        Some(h) => {
+            let header_dict = PyDict::new(py);
+            header_dict.set_item("start", h.content_start)?;
+            header_dict.set_item("end", h.content_end)?;
+            header_dict.set_item("fields", parse_header_fields(data, h))?;
+            result.set_item("header", header_dict)?;
+        }
+        None => result.set_item("header", py.None())?,
+    }
+
+    let documents = PyList::empty(py);
+    for &doc_idx in &tree.nodes[tree.root as usize].children {
+        let doc = &tree.nodes[doc_idx as usize];
+        if doc.tag != "DOCUMENT" {
+            continue;
+        }
+
+        let doc_dict = PyDict::new(py);
+        doc_dict.set_item(
+            "type",
+            find_child(&tree, doc_idx, "TYPE").map(|n| field_value(data, n)),
+        )?;
+        doc_dict.set_item(
+            "sequence",
+            find_child(&tree, doc_idx, "SEQUENCE").map(|n| field_value(data, n)),
+        )?;
+        doc_dict.set_item(
+            "filename",
+            find_child(&tree, doc_idx, "FILENAME").map(|n| field_value(data, n)),
+        )?;
+        doc_dict.set_item(
+            "description",
+            find_child(&tree, doc_idx, "DESCRIPTION").map(|n| field_value(data, n)),
+        )?;
+        let text_range = find_child(&tree, doc_idx, "TEXT").map(|n| (n.content_start, n.content_end));
+        doc_dict.set_item("text_range", text_range)?;
+
+        documents.append(doc_dict)?;
+    }
+    result.set_item("documents", documents)?;
+
+    Ok(result)
+}
+
+/// Read a single-line field (no closing tag) out of a document slice, e.g.
+/// the value of `<FILENAME>` or `<SEQUENCE>`.
+fn extract_field_value(slice: &[u8], tag: &[u8]) -> Option<String> {
+    let pos = find_subsequence(slice, tag)?;
+    let start = pos + tag.len();
+    let end = slice[start..]
+        .iter()
+        .position(|&b| b == b'\n')
+        .map_or(slice.len(), |o| start + o);
+    let value = String::from_utf8_lossy(&slice[start..end]).trim().to_string();
+    if value.is_empty() {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+/// Reject a filing-supplied name as an output filename unless it is a bare
+/// basename: no `/` or `\`, and not `.`/`..`. `data` is untrusted submission
+/// content written straight to disk, so a `<FILENAME>` of `../../etc/passwd`
+/// or an absolute path must not be allowed to escape `output_dir` (`Path::join`
+/// with an absolute component discards the base entirely).
+fn sanitize_output_name(name: String) -> Option<String> {
+    if name.is_empty()
+        || name == "."
+        || name == ".."
+        || name.contains('/')
+        || name.contains('\\')
+    {
+        return None;
+    }
+    Some(name)
+}
+
+/// Derive the output filename for one document slice, preferring its
+/// `<FILENAME>` metadata, then `<SEQUENCE>`, then the document's position.
+/// Any candidate that isn't a safe bare basename is discarded in favor of
+/// the next fallback.
+fn document_output_name(slice: &[u8], seq_idx: usize) -> String {
+    extract_field_value(slice, b"<FILENAME>")
+        .and_then(sanitize_output_name)
+        .or_else(|| {
+            extract_field_value(slice, b"<SEQUENCE>")
+                .map(|seq| format!("{}.txt", seq))
+                .and_then(sanitize_output_name)
+        })
+        .unwrap_or_else(|| format!("document_{}.txt", seq_idx))
+}
+
+/// Split a complete submission file into per-document files without ever
+/// loading the whole input into Python memory.
+///
+/// Memory-maps `input_path` (read-only), locates document boundaries over
+/// the mapped region, and writes each document slice to its own file under
+/// `output_dir` in parallel. Since the mmap is read-only and every writer
+/// targets a distinct file, the parallel phase needs no locking; each slice
+/// streams straight through a buffered writer instead of being collected
+/// first. Returns the paths written, in document order.
+#[pyfunction]
+fn split_file_to_dir(input_path: &str, output_dir: &str) -> PyResult<Vec<PathBuf>> {
+    let file = std::fs::File::open(input_path).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let mmap = unsafe { Mmap::map(&file) }.map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let data: &[u8] = &mmap;
+
+    std::fs::create_dir_all(output_dir).map_err(|e| PyIOError::new_err(e.to_string()))?;
+    let output_dir = Path::new(output_dir);
+
+    let boundaries = find_document_boundaries(data);
+
+    boundaries
+        .par_iter()
+        .enumerate()
+        .map(|(seq_idx, &(start, end))| -> std::io::Result<PathBuf> {
+            let slice = &data[start..end];
+            let path = output_dir.join(document_output_name(slice, seq_idx));
+            let mut writer = std::io::BufWriter::new(std::fs::File::create(&path)?);
+            writer.write_all(slice)?;
+            Ok(path)
+        })
+        .collect::<std::io::Result<Vec<PathBuf>>>()
+        .map_err(|e| PyIOError::new_err(e.to_string()))
+}
+
+/// Collect every `<DOCUMENT>`/`</DOCUMENT>` pairing in `data` without
+/// assuming the tags are sequential or non-overlapping, unlike
+/// `find_document_boundaries`, which stops at the first unmatched tag.
+///
+/// Locates every open/close tag with a single Aho-Corasick pass, then walks
+/// the combined match list maintaining a stack of open start offsets: each
+/// close tag pairs with the most recently opened, not-yet-closed start —
+/// standard bracket matching — so a `<DOCUMENT>` nested inside another pairs
+/// with its own inner close while the outer `<DOCUMENT>` still reaches its
+/// own outer close, instead of both collapsing onto the first close seen.
+/// A close with no open on the stack, or an open left dangling at the end,
+/// is simply dropped; `merged_document_boundaries` then resolves any
+/// remaining overlap between the resulting candidate ranges.
+fn candidate_document_ranges(data: &[u8]) -> Vec<(usize, usize)> {
+    const START_TAG: &[u8] = b"<DOCUMENT>";
+    const END_TAG: &[u8] = b"</DOCUMENT>";
+    const START_ID: usize = 0;
+    const END_ID: usize = 1;
+
+    let automaton = AhoCorasick::new(&[START_TAG, END_TAG]);
+    let mut matches = automaton.find_all(data);
+    matches.sort_by_key(|&(_, start)| start);
+
+    let mut open_stack: Vec<usize> = Vec::new();
+    let mut candidates = Vec::new();
+    for (pattern_id, start) in matches {
+        if pattern_id == START_ID {
+            open_stack.push(start);
+        } else if pattern_id == END_ID {
+            if let Some(doc_start) = open_stack.pop() {
+                candidates.push((doc_start, start + END_TAG.len()));
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Merge overlapping/nested candidate document ranges into a deduplicated,
+/// non-overlapping set.
+///
+/// Sorts candidates by start offset and sweeps once, folding any range whose
+/// start falls at or before the running interval's end into that interval —
+/// the same coverage-merge used for merging `(lo, hi)` intervals anywhere
+/// else. This makes boundary detection robust to nested `<DOCUMENT>` tags
+/// that would otherwise truncate `find_document_boundaries`.
+///
+/// Adjacency (`start == last.1 + 1`) is deliberately NOT merged: real EDGAR
+/// submissions lay documents out back-to-back as `</DOCUMENT>\n<DOCUMENT>`,
+/// so every consecutive document would otherwise collapse into one interval
+/// and swallow the inter-document gaps `document_gaps` is meant to expose.
+#[pyfunction]
+fn merged_document_boundaries(data: &[u8]) -> Vec<(usize, usize)> {
+    let mut candidates = candidate_document_ranges(data);
+    candidates.sort_by_key(|&(start, _)| start);
+
+    let mut merged: Vec<(usize, usize)> = Vec::new();
+    for (start, end) in candidates {
+        if let Some(last) = merged.last_mut() {
+            if start <= last.1 {
+                last.1 = last.1.max(end);
+                continue;
+            }
+        }
+        merged.push((start, end));
+    }
+    merged
+}
+
+/// Return the complement of `merged_document_boundaries`: the byte ranges
+/// not covered by any document, i.e. the `<SEC-HEADER>` block, inter-document
+/// whitespace, and any trailer after the last document.
+#[pyfunction]
+fn document_gaps(data: &[u8]) -> Vec<(usize, usize)> {
+    let merged = merged_document_boundaries(data);
+
+    let mut gaps = Vec::new();
+    let mut cursor = 0usize;
+    for &(start, end) in &merged {
+        if cursor < start {
+            gaps.push((cursor, start));
+        }
+        cursor = cursor.max(end);
+    }
+    if cursor < data.len() {
+        gaps.push((cursor, data.len()));
+    }
+    gaps
+}
+
+/// Rolling-hash window size, in bytes, for content-defined chunking.
+const CDC_WINDOW: usize = 48;
+/// Boundary mask: a window hashes to a boundary when `h & CDC_MASK == 0`.
+/// `(1 << 13) - 1` targets an average chunk size of ~8 KB.
+const CDC_MASK: u64 = (1 << 13) - 1;
+/// Minimum chunk length, to bound variance from very early boundaries.
+const CDC_MIN_CHUNK: usize = 2 * 1024;
+/// Maximum chunk length; a boundary is forced here even without a hash hit.
+const CDC_MAX_CHUNK: usize = 64 * 1024;
+/// Base for the rolling polynomial hash over the CDC window.
+const CDC_BASE: u64 = 257;
+
+/// Split `data` into content-defined chunks and fingerprint each one.
+///
+/// Maintains a rolling hash over a fixed `CDC_WINDOW`-byte window,
+/// `h = h*B + byte_in - byte_out*B^w`, and declares a boundary wherever the
+/// low bits of `h` selected by `CDC_MASK` are all zero, subject to `CDC_MIN_CHUNK`/
+/// `CDC_MAX_CHUNK`. Because boundaries are anchored to content rather than
+/// position, an edit early in a buffer only disturbs the chunk it falls in —
+/// the rest keep their original fingerprints, so near-duplicate content
+/// (boilerplate reused with small edits) still shares most chunks with the
+/// original. Returns `(offset, len, fingerprint)` for each chunk, where
+/// `fingerprint` is FNV-1a over the chunk's bytes.
+#[pyfunction]
+fn chunk_content(data: &[u8]) -> Vec<(usize, usize, u64)> {
+    if data.is_empty() {
+        return Vec::new();
+    }
+
+    let base_pow_window = CDC_BASE.wrapping_pow(CDC_WINDOW as u32);
+
+    let mut chunks = Vec::new();
+    let mut chunk_start = 0usize;
+    let mut h: u64 = 0;
+
+    for i in 0..data.len() {
+        h = h.wrapping_mul(CDC_BASE).wrapping_add(data[i] as u64);
+        if i >= chunk_start + CDC_WINDOW {
+            let byte_out = data[i - CDC_WINDOW] as u64;
+            h = h.wrapping_sub(byte_out.wrapping_mul(base_pow_window));
+        }
+
+        let window_len = i - chunk_start + 1;
+        let hit_boundary = window_len >= CDC_WINDOW && (h & CDC_MASK == 0);
+        let forced_boundary = window_len >= CDC_MAX_CHUNK;
+
+        if forced_boundary || (hit_boundary && window_len >= CDC_MIN_CHUNK) {
+            let end = i + 1;
+            let fingerprint = fnv1a_hash(&data[chunk_start..end]);
+            chunks.push((chunk_start, end - chunk_start, fingerprint));
+            chunk_start = end;
+            h = 0;
+        }
+    }
+
+    if chunk_start < data.len() {
+        let fingerprint = fnv1a_hash(&data[chunk_start..]);
+        chunks.push((chunk_start, data.len() - chunk_start, fingerprint));
+    }
+
+    chunks
+}
+
+/// Chunk a batch of documents in parallel and find fingerprints shared
+/// across more than one document.
+///
+/// Returns `(per_document_fingerprints, shared_fingerprint_counts)`: the
+/// first is each document's ordered list of chunk fingerprints, the second
+/// maps a fingerprint to how many distinct documents contain it, restricted
+/// to fingerprints seen in more than one — i.e. candidate shared/duplicated
+/// blocks (boilerplate, disclaimers, exhibit templates) across the corpus.
+#[pyfunction]
+fn dedup_chunks(contents: Vec<Vec<u8>>) -> (Vec<Vec<u64>>, HashMap<u64, usize>) {
+    let per_document: Vec<Vec<u64>> = contents
+        .par_iter()
+        .map(|data| {
+            chunk_content(data)
+                .into_iter()
+                .map(|(_, _, fingerprint)| fingerprint)
+                .collect()
+        })
+        .collect();
+
+    let mut doc_counts: HashMap<u64, usize> = HashMap::new();
+    for fingerprints in &per_document {
+        let unique: HashSet<u64> = fingerprints.iter().copied().collect();
+        for fingerprint in unique {
+            *doc_counts.entry(fingerprint).or_insert(0) += 1;
+        }
+    }
+    doc_counts.retain(|_, &mut count| count > 1);
+
+    (per_document, doc_counts)
+}
+
 /// Python module definition
 #[pymodule]
 fn sec_parsers(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -167,5 +849,92 @@ fn sec_parsers(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(hash_content_batch, m)?)?;
     m.add_function(wrap_pyfunction!(count_lines, m)?)?;
     m.add_function(wrap_pyfunction!(extract_tag_content, m)?)?;
+    m.add_function(wrap_pyfunction!(find_all, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_submission, m)?)?;
+    m.add_function(wrap_pyfunction!(split_file_to_dir, m)?)?;
+    m.add_function(wrap_pyfunction!(merged_document_boundaries, m)?)?;
+    m.add_function(wrap_pyfunction!(document_gaps, m)?)?;
+    m.add_function(wrap_pyfunction!(chunk_content, m)?)?;
+    m.add_function(wrap_pyfunction!(dedup_chunks, m)?)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merged_document_boundaries_keeps_back_to_back_documents_separate() {
+        // Real EDGAR layout: </DOCUMENT>\n<DOCUMENT>, i.e. the next start is
+        // exactly the previous end + 1. These must NOT collapse into one
+        // interval, or document_gaps loses the inter-document gap entirely.
+        let data = b"<DOCUMENT>AAA</DOCUMENT>\n<DOCUMENT>BBB</DOCUMENT>";
+
+        let merged = merged_document_boundaries(data);
+        assert_eq!(merged, vec![(0, 24), (25, 49)]);
+
+        let gaps = document_gaps(data);
+        assert_eq!(gaps, vec![(24, 25)]);
+    }
+
+    #[test]
+    fn merged_document_boundaries_merges_genuine_overlap() {
+        // A <DOCUMENT> nested inside another: stack-based pairing gives the
+        // inner tag its own close ((11,33)) and the outer tag its own close
+        // ((0,45)) rather than both collapsing onto the first close seen.
+        // Those two candidates overlap and should merge into one interval
+        // spanning the whole (properly nested) outer document.
+        let data = b"<DOCUMENT>A<DOCUMENT>B</DOCUMENT>C</DOCUMENT>";
+        assert_eq!(data.len(), 45);
+
+        let merged = merged_document_boundaries(data);
+        assert_eq!(merged, vec![(0, 45)]);
+    }
+
+    #[test]
+    fn chunk_content_covers_input_contiguously() {
+        let data = vec![b'x'; 50_000];
+
+        let chunks = chunk_content(&data);
+        assert!(!chunks.is_empty());
+
+        let mut cursor = 0usize;
+        for (offset, len, fingerprint) in &chunks {
+            assert_eq!(*offset, cursor);
+            assert!(*len <= CDC_MAX_CHUNK);
+            assert_eq!(*fingerprint, fnv1a_hash(&data[*offset..*offset + *len]));
+            cursor += len;
+        }
+        assert_eq!(cursor, data.len());
+    }
+
+    #[test]
+    fn chunk_content_is_deterministic() {
+        let data: Vec<u8> = (0..20_000).map(|i| (i % 251) as u8).collect();
+        assert_eq!(chunk_content(&data), chunk_content(&data));
+    }
+
+    #[test]
+    fn chunk_content_shares_fingerprints_despite_early_insertion() {
+        // An edit near the start should only disturb the chunk(s) around it;
+        // content-defined boundaries should let most later fingerprints
+        // survive unchanged, unlike fixed-size chunking.
+        // Pseudo-random bytes: a short-period buffer has too few distinct
+        // windows to ever hit the boundary mask, leaving one forced chunk.
+        let mut x: u32 = 12345;
+        let base: Vec<u8> = (0..40_000)
+            .map(|_| {
+                x = x.wrapping_mul(1103515245).wrapping_add(12345);
+                (x >> 16) as u8
+            })
+            .collect();
+        let mut edited = base.clone();
+        edited.splice(10..10, std::iter::repeat(b'!').take(5));
+
+        let base_fps: HashSet<u64> = chunk_content(&base).into_iter().map(|(_, _, fp)| fp).collect();
+        let edited_fps: HashSet<u64> = chunk_content(&edited).into_iter().map(|(_, _, fp)| fp).collect();
+
+        let shared = base_fps.intersection(&edited_fps).count();
+        assert!(shared > 0, "expected at least some chunks to survive a small early edit");
+    }
+}